@@ -0,0 +1,159 @@
+//! Waku content topics
+//! as per the [specification](https://rfc.vac.dev/spec/23/#content-topics)
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A content topic, e.g. `/toychat/2/huilong/proto`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WakuContentTopic {
+    application: Cow<'static, str>,
+    version: u32,
+    content_topic_name: Cow<'static, str>,
+    encoding: Cow<'static, str>,
+}
+
+impl WakuContentTopic {
+    pub fn new(
+        application: impl Into<Cow<'static, str>>,
+        version: u32,
+        content_topic_name: impl Into<Cow<'static, str>>,
+        encoding: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            application: application.into(),
+            version,
+            content_topic_name: content_topic_name.into(),
+            encoding: encoding.into(),
+        }
+    }
+
+    pub fn application(&self) -> &str {
+        &self.application
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn content_topic_name(&self) -> &str {
+        &self.content_topic_name
+    }
+
+    pub fn encoding(&self) -> &str {
+        &self.encoding
+    }
+}
+
+impl fmt::Display for WakuContentTopic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "/{}/{}/{}/{}",
+            self.application, self.version, self.content_topic_name, self.encoding
+        )
+    }
+}
+
+/// The error returned when a string doesn't match the
+/// `/application/version/content_topic_name/encoding` wire format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseContentTopicError(String);
+
+impl fmt::Display for ParseContentTopicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid content topic {:?}: expected /application/version/content_topic_name/encoding",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseContentTopicError {}
+
+impl FromStr for WakuContentTopic {
+    type Err = ParseContentTopicError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut parts = value.splitn(5, '/');
+        let (Some(""), Some(application), Some(version), Some(content_topic_name), Some(encoding)) =
+            (parts.next(), parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(ParseContentTopicError(value.to_string()));
+        };
+
+        let version: u32 = version
+            .parse()
+            .map_err(|_| ParseContentTopicError(value.to_string()))?;
+
+        Ok(WakuContentTopic::new(
+            application.to_string(),
+            version,
+            content_topic_name.to_string(),
+            encoding.to_string(),
+        ))
+    }
+}
+
+// Content topics are carried on the wire as the plain string from `Display`/`FromStr`
+// (e.g. `/toychat/2/huilong/proto`), matching `PubsubTopic`'s convention for
+// string-shaped topic types, rather than as a JSON object of their fields.
+impl Serialize for WakuContentTopic {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for WakuContentTopic {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn displays_as_the_wire_format_string() {
+        let content_topic = WakuContentTopic::new("toychat", 2, "huilong", "proto");
+        assert_eq!(content_topic.to_string(), "/toychat/2/huilong/proto");
+    }
+
+    #[test]
+    fn parses_the_wire_format_string() {
+        let content_topic: WakuContentTopic = "/toychat/2/huilong/proto".parse().unwrap();
+        assert_eq!(content_topic, WakuContentTopic::new("toychat", 2, "huilong", "proto"));
+    }
+
+    #[test]
+    fn rejects_a_malformed_string() {
+        assert!("toychat/2/huilong/proto".parse::<WakuContentTopic>().is_err());
+        assert!("/toychat/not-a-number/huilong/proto"
+            .parse::<WakuContentTopic>()
+            .is_err());
+    }
+
+    #[test]
+    fn serializes_as_a_plain_json_string() {
+        let content_topic = WakuContentTopic::new("toychat", 2, "huilong", "proto");
+        assert_eq!(
+            serde_json::to_string(&content_topic).unwrap(),
+            "\"/toychat/2/huilong/proto\""
+        );
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let content_topic = WakuContentTopic::new("toychat", 2, "huilong", "proto");
+        let json = serde_json::to_string(&content_topic).unwrap();
+        let decoded: WakuContentTopic = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, content_topic);
+    }
+}
@@ -0,0 +1,33 @@
+//! Decoding of libwaku's JSON FFI responses
+//! as per the [specification](https://rfc.vac.dev/spec/36/#jsonresponse-type)
+
+use serde::de::DeserializeOwned;
+
+use crate::general::Result;
+
+/// Raw response delivered through libwaku's single event/result callback.
+#[derive(Debug, Clone, Default)]
+pub enum LibwakuResponse {
+    #[default]
+    MissingCallback,
+    Success(String),
+    Failure(String),
+}
+
+pub(crate) fn handle_response<T: DeserializeOwned>(response: LibwakuResponse) -> Result<T> {
+    match response {
+        LibwakuResponse::Success(payload) => {
+            serde_json::from_str(&payload).map_err(|error| error.to_string())
+        }
+        LibwakuResponse::Failure(error) => Err(error),
+        LibwakuResponse::MissingCallback => Err("missing callback".to_string()),
+    }
+}
+
+pub(crate) fn handle_no_response(response: LibwakuResponse) -> Result<()> {
+    match response {
+        LibwakuResponse::Success(_) => Ok(()),
+        LibwakuResponse::Failure(error) => Err(error),
+        LibwakuResponse::MissingCallback => Err("missing callback".to_string()),
+    }
+}
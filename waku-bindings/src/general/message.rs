@@ -0,0 +1,89 @@
+//! Waku message
+//! as per the [specification](https://rfc.vac.dev/spec/14/)
+
+use serde::{Deserialize, Serialize};
+
+use crate::general::contenttopic::WakuContentTopic;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WakuMessage {
+    #[serde(with = "payload_base64")]
+    payload: Vec<u8>,
+    content_topic: WakuContentTopic,
+    version: Option<u32>,
+    timestamp: Option<u64>,
+    ephemeral: Option<bool>,
+}
+
+impl WakuMessage {
+    pub fn new(payload: Vec<u8>, content_topic: WakuContentTopic, timestamp: u64) -> Self {
+        Self {
+            payload,
+            content_topic,
+            version: None,
+            timestamp: Some(timestamp),
+            ephemeral: None,
+        }
+    }
+
+    pub fn content_topic(&self) -> &WakuContentTopic {
+        &self.content_topic
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp.unwrap_or_default()
+    }
+}
+
+/// `payload` is carried on the wire as a base64 string rather than a JSON array
+/// of bytes, as per the [specification](https://rfc.vac.dev/spec/14/#payload).
+mod payload_base64 {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        payload: &[u8],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        STANDARD.encode(payload).serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        STANDARD.decode(encoded).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn serializes_payload_as_base64() {
+        let message = WakuMessage::new(
+            vec![1, 2, 3],
+            WakuContentTopic::new("toychat", 2, "huilong", "proto"),
+            1700000000,
+        );
+
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["payload"], "AQID");
+    }
+
+    #[test]
+    fn round_trips_payload_through_json() {
+        let message = WakuMessage::new(
+            vec![1, 2, 3],
+            WakuContentTopic::new("toychat", 2, "huilong", "proto"),
+            1700000000,
+        );
+
+        let json = serde_json::to_string(&message).unwrap();
+        let decoded: WakuMessage = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, message);
+    }
+}
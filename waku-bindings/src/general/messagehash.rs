@@ -0,0 +1,27 @@
+//! Waku message hash
+//! as per the [specification](https://rfc.vac.dev/spec/14/#message-hash)
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MessageHash(String);
+
+impl From<String> for MessageHash {
+    fn from(value: String) -> Self {
+        MessageHash(value)
+    }
+}
+
+impl From<&str> for MessageHash {
+    fn from(value: &str) -> Self {
+        MessageHash(value.to_string())
+    }
+}
+
+impl fmt::Display for MessageHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
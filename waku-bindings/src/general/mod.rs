@@ -0,0 +1,13 @@
+//! General types shared across the waku-bindings crate
+
+pub mod contenttopic;
+pub(crate) mod libwaku_response;
+mod message;
+pub mod messagehash;
+pub mod pubsubtopic;
+
+pub use message::WakuMessage;
+
+/// Result type returned by every FFI-backed call: `Err` carries the error
+/// message reported by libwaku.
+pub type Result<T> = std::result::Result<T, String>;
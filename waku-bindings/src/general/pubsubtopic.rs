@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::general::contenttopic::WakuContentTopic;
+use crate::general::Result;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -21,3 +25,86 @@ impl Into<Vec<u8>> for PubsubTopic {
         self.0.into()
     }
 }
+
+/// Parameters needed to derive a pubsub topic from a content topic under the
+/// autosharding scheme described in [RFC 51](https://rfc.vac.dev/spec/51/#automatic-sharding).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShardingConfig {
+    pub cluster_id: u16,
+    pub shard_count: u16,
+}
+
+impl PubsubTopic {
+    /// Derive the pubsub topic a content topic is autosharded onto, as per
+    /// [RFC 51](https://rfc.vac.dev/spec/51/#automatic-sharding):
+    /// `shard = sha256(application || version)[-8:] % shard_count`, formatted as
+    /// `/waku/2/rs/{cluster_id}/{shard}`.
+    pub fn autoshard(content_topic: &WakuContentTopic, config: &ShardingConfig) -> Result<Self> {
+        if config.shard_count == 0 {
+            //TODO add error types
+            return Err("ShardingConfig.shard_count must be non-zero".to_string());
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(content_topic.application().as_bytes());
+        hasher.update(content_topic.version().to_string().as_bytes());
+        let digest = hasher.finalize();
+
+        let mut tail = [0u8; 8];
+        tail.copy_from_slice(&digest[digest.len() - 8..]);
+        let value = u64::from_be_bytes(tail);
+
+        let shard = value % config.shard_count as u64;
+        Ok(PubsubTopic(format!("/waku/2/rs/{}/{}", config.cluster_id, shard)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn autoshard_derives_deterministic_pubsub_topic() {
+        let content_topic = WakuContentTopic::new("toychat", 2, "huilong", "proto");
+        let config = ShardingConfig {
+            cluster_id: 1,
+            shard_count: 8,
+        };
+
+        let pubsub_topic = PubsubTopic::autoshard(&content_topic, &config).unwrap();
+
+        assert_eq!(pubsub_topic, PubsubTopic::from("/waku/2/rs/1/3"));
+        // Deterministic: the same content topic always maps to the same shard.
+        assert_eq!(
+            pubsub_topic,
+            PubsubTopic::autoshard(&content_topic, &config).unwrap()
+        );
+    }
+
+    #[test]
+    fn autoshard_uses_version_in_the_hash() {
+        let config = ShardingConfig {
+            cluster_id: 1,
+            shard_count: 16,
+        };
+        let v1 = WakuContentTopic::new("toychat", 1, "huilong", "proto");
+        let v2 = WakuContentTopic::new("toychat", 2, "huilong", "proto");
+
+        assert_ne!(
+            PubsubTopic::autoshard(&v1, &config).unwrap(),
+            PubsubTopic::autoshard(&v2, &config).unwrap()
+        );
+    }
+
+    #[test]
+    fn autoshard_rejects_zero_shard_count() {
+        let content_topic = WakuContentTopic::new("toychat", 2, "huilong", "proto");
+        let config = ShardingConfig {
+            cluster_id: 1,
+            shard_count: 0,
+        };
+
+        assert!(PubsubTopic::autoshard(&content_topic, &config).is_err());
+    }
+}
@@ -0,0 +1,23 @@
+//! Waku node configuration
+//! as per the [specification](https://rfc.vac.dev/spec/36/#jsonconfig-type)
+
+use serde::{Deserialize, Serialize};
+
+use crate::general::pubsubtopic::ShardingConfig;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RLNConfig {
+    pub relay: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WakuNodeConfig {
+    pub relay: Option<bool>,
+    pub rln_relay: Option<RLNConfig>,
+    /// Cluster/shard parameters used to derive pubsub topics from content topics.
+    /// See [`PubsubTopic::autoshard`](crate::general::pubsubtopic::PubsubTopic::autoshard).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sharding: Option<ShardingConfig>,
+}
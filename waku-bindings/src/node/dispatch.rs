@@ -0,0 +1,88 @@
+//! Fan-out of the single libwaku event callback to multiple subscribers.
+//!
+//! libwaku only allows one callback to be registered per node via
+//! `waku_set_event_callback`. An [`EventDispatcher`] is installed once, at node
+//! creation, and re-broadcasts every event to each live subscriber, so that
+//! `response_stream`/`event_stream`/`message_stream` and internal consumers such
+//! as the relay cache can all subscribe independently instead of clobbering
+//! whichever one last called `waku_set_event_callback`.
+
+use std::sync::Mutex;
+
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+use crate::general::libwaku_response::LibwakuResponse;
+
+#[derive(Default)]
+pub(crate) struct EventDispatcher {
+    subscribers: Mutex<Vec<UnboundedSender<LibwakuResponse>>>,
+}
+
+impl EventDispatcher {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new subscriber, returning a stream of every event dispatched
+    /// from this point onward.
+    pub(crate) fn subscribe(&self) -> UnboundedReceiverStream<LibwakuResponse> {
+        let (tx, rx) = unbounded_channel();
+        self.subscribers
+            .lock()
+            .expect("dispatcher lock should not be poisoned")
+            .push(tx);
+        UnboundedReceiverStream::new(rx)
+    }
+
+    /// Broadcast an event to every live subscriber, dropping any whose receiver
+    /// has gone away.
+    pub(crate) fn dispatch(&self, event: LibwakuResponse) {
+        let mut subscribers = self
+            .subscribers
+            .lock()
+            .expect("dispatcher lock should not be poisoned");
+
+        subscribers.retain(|tx| tx.send(event.clone()).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tokio_stream::StreamExt;
+
+    #[tokio::test]
+    async fn dispatch_delivers_one_event_to_every_subscriber() {
+        let dispatcher = EventDispatcher::new();
+        let mut first = dispatcher.subscribe();
+        let mut second = dispatcher.subscribe();
+
+        dispatcher.dispatch(LibwakuResponse::Success("event".to_string()));
+
+        assert!(matches!(
+            first.next().await,
+            Some(LibwakuResponse::Success(payload)) if payload == "event"
+        ));
+        assert!(matches!(
+            second.next().await,
+            Some(LibwakuResponse::Success(payload)) if payload == "event"
+        ));
+    }
+
+    #[tokio::test]
+    async fn dispatch_prunes_subscribers_whose_receiver_was_dropped() {
+        let dispatcher = EventDispatcher::new();
+        let dropped = dispatcher.subscribe();
+        let mut live = dispatcher.subscribe();
+        drop(dropped);
+
+        dispatcher.dispatch(LibwakuResponse::Success("first".to_string()));
+        assert_eq!(dispatcher.subscribers.lock().unwrap().len(), 1);
+
+        assert!(matches!(
+            live.next().await,
+            Some(LibwakuResponse::Success(payload)) if payload == "first"
+        ));
+    }
+}
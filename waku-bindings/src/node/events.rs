@@ -0,0 +1,102 @@
+//! Typed Waku node events, decoded from the untyped [`LibwakuResponse`] event callback
+
+use serde::Deserialize;
+
+use crate::general::contenttopic::WakuContentTopic;
+use crate::general::libwaku_response::LibwakuResponse;
+use crate::general::messagehash::MessageHash;
+use crate::general::pubsubtopic::PubsubTopic;
+use crate::general::WakuMessage;
+
+/// A message received over Relay or Filter.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WakuMessageEvent {
+    pub pubsub_topic: PubsubTopic,
+    pub message_hash: MessageHash,
+    pub waku_message: WakuMessage,
+}
+
+impl WakuMessageEvent {
+    pub fn content_topic(&self) -> &WakuContentTopic {
+        self.waku_message.content_topic()
+    }
+}
+
+/// A strongly typed Waku node event.
+#[derive(Debug, Clone)]
+pub enum WakuEvent {
+    Message(WakuMessageEvent),
+    /// An event whose `eventType` this crate doesn't model yet, carrying that tag.
+    Unrecognized(String),
+}
+
+/// Event payloads are delivered through the callback enveloped as
+/// `{"eventType": "...", "event": {...}}`, as per the
+/// [specification](https://rfc.vac.dev/spec/36/#extern-char-waku_set_event_callbackwakucallback-cb),
+/// so that multiple event kinds can share the one callback.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EventEnvelope {
+    event_type: String,
+    event: serde_json::Value,
+}
+
+impl WakuEvent {
+    /// Decode a raw event callback payload into a [`WakuEvent`], discarding
+    /// responses that aren't events (e.g. FFI call results or failures).
+    pub(crate) fn decode(response: &LibwakuResponse) -> Option<Self> {
+        let LibwakuResponse::Success(payload) = response else {
+            return None;
+        };
+
+        let envelope: EventEnvelope = serde_json::from_str(payload).ok()?;
+
+        match envelope.event_type.as_str() {
+            "message" => serde_json::from_value(envelope.event)
+                .ok()
+                .map(WakuEvent::Message),
+            event_type => Some(WakuEvent::Unrecognized(event_type.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode_parses_a_message_event_envelope() {
+        let payload = r#"{
+            "eventType": "message",
+            "event": {
+                "pubsubTopic": "/waku/2/rs/1/7",
+                "messageHash": "0xabc123",
+                "wakuMessage": {
+                    "payload": "AQID",
+                    "contentTopic": "/toychat/2/huilong/proto",
+                    "version": 0,
+                    "timestamp": 1700000000,
+                    "ephemeral": false
+                }
+            }
+        }"#;
+
+        let event = WakuEvent::decode(&LibwakuResponse::Success(payload.to_string()))
+            .expect("a message envelope should decode");
+
+        let WakuEvent::Message(message_event) = event else {
+            panic!("expected WakuEvent::Message, got {event:?}");
+        };
+        assert_eq!(message_event.content_topic().application(), "toychat");
+    }
+
+    #[test]
+    fn decode_falls_back_to_unrecognized_for_other_event_types() {
+        let payload = r#"{"eventType": "connectionChange", "event": {}}"#;
+
+        let event = WakuEvent::decode(&LibwakuResponse::Success(payload.to_string())).unwrap();
+
+        assert!(matches!(event, WakuEvent::Unrecognized(tag) if tag == "connectionChange"));
+    }
+}
@@ -2,34 +2,40 @@
 
 mod config;
 mod context;
+mod dispatch;
 mod events;
 mod filter;
 mod lightpush;
 mod management;
 mod peers;
 mod relay;
+mod relay_cache;
 mod store;
 
 // std
+use async_stream::try_stream;
 pub use aes_gcm::Key;
 pub use multiaddr::Multiaddr;
 pub use secp256k1::{PublicKey, SecretKey};
 use std::marker::PhantomData;
+use std::sync::Arc;
 use std::time::Duration;
 use store::{StoreQueryRequest, StoreWakuMessageResponse};
-use tokio::sync::mpsc::unbounded_channel;
-use tokio_stream::wrappers::UnboundedReceiverStream;
-use tokio_stream::Stream;
+use tokio::task::JoinHandle;
+use tokio_stream::{Stream, StreamExt};
 // internal
 use crate::general::contenttopic::WakuContentTopic;
 use crate::general::libwaku_response::LibwakuResponse;
-pub use crate::general::pubsubtopic::PubsubTopic;
+pub use crate::general::pubsubtopic::{PubsubTopic, ShardingConfig};
 use crate::general::{messagehash::MessageHash, Result, WakuMessage};
 
 use crate::node::context::WakuNodeContext;
+use crate::node::dispatch::EventDispatcher;
+use crate::node::relay_cache::RelayCache;
 pub use config::RLNConfig;
 pub use config::WakuNodeConfig;
 pub use events::{WakuEvent, WakuMessageEvent};
+pub use peers::{Connectedness, PeerInfo};
 
 // Define state marker types
 pub struct Initialized;
@@ -39,6 +45,12 @@ pub struct Running;
 pub struct WakuNodeHandle<State> {
     ctx: WakuNodeContext,
     config: WakuNodeConfig,
+    cache: Arc<RelayCache>,
+    dispatcher: Arc<EventDispatcher>,
+    /// The task forwarding dispatched events into `cache` while the node is
+    /// running. `None` when `Initialized`; aborted and dropped by `stop()` so
+    /// that a later `start()` doesn't leave a duplicate forwarder behind.
+    relay_cache_task: Option<JoinHandle<()>>,
     _state: PhantomData<State>,
 }
 
@@ -53,6 +65,41 @@ impl<State> WakuNodeHandle<State> {
         self.ctx.reset_ptr();
         res
     }
+
+    /// Return a stream of all Waku responses.
+    pub fn response_stream(&self) -> impl Stream<Item = LibwakuResponse> {
+        self.dispatcher.subscribe()
+    }
+
+    /// Return a stream of strongly typed [`WakuEvent`]s, decoded from the underlying
+    /// [`response_stream`](Self::response_stream).
+    pub fn event_stream(&self) -> impl Stream<Item = WakuEvent> {
+        self.response_stream()
+            .filter_map(|response| async move { WakuEvent::decode(&response) })
+    }
+
+    /// Return a stream of decoded [`WakuMessageEvent`]s, optionally keeping only
+    /// messages whose content topic is in `filter`.
+    pub fn message_stream(
+        &self,
+        filter: Option<Vec<WakuContentTopic>>,
+    ) -> impl Stream<Item = WakuMessageEvent> {
+        self.event_stream().filter_map(move |event| {
+            let filter = filter.clone();
+            async move {
+                let WakuEvent::Message(event) = event else {
+                    return None;
+                };
+
+                match &filter {
+                    Some(content_topics) if !content_topics.contains(event.content_topic()) => {
+                        None
+                    }
+                    _ => Some(event),
+                }
+            }
+        })
+    }
 }
 
 impl WakuNodeHandle<Initialized> {
@@ -63,9 +110,18 @@ impl WakuNodeHandle<Initialized> {
 
         let ctx = management::waku_new(&config).await?;
 
+        let dispatcher = Arc::new(EventDispatcher::new());
+        let dispatcher_clone = dispatcher.clone();
+        ctx.waku_set_event_callback(move |event: LibwakuResponse| {
+            dispatcher_clone.dispatch(event);
+        })?;
+
         let node = Self {
             ctx,
             config,
+            cache: Arc::new(RelayCache::new(None)),
+            dispatcher,
+            relay_cache_task: None,
             _state: PhantomData,
         };
 
@@ -77,41 +133,30 @@ impl WakuNodeHandle<Initialized> {
     pub async fn start(self) -> Result<WakuNodeHandle<Running>> {
         management::waku_start(&self.ctx).await?;
 
+        // Populate the relay cache from the same dispatcher `response_stream`/
+        // `event_stream` subscribe to, rather than re-registering libwaku's single
+        // event callback (which would clobber any subscriber set up before `start`).
+        let cache = self.cache.clone();
+        let mut events = self.dispatcher.subscribe();
+        let relay_cache_task = tokio::spawn(async move {
+            while let Some(response) = events.next().await {
+                if let Some(WakuEvent::Message(event)) = WakuEvent::decode(&response) {
+                    cache.insert(event);
+                }
+            }
+        });
+
         let running_node = WakuNodeHandle {
             ctx: self.ctx,
             config: self.config,
+            cache: self.cache,
+            dispatcher: self.dispatcher,
+            relay_cache_task: Some(relay_cache_task),
             _state: PhantomData,
         };
 
         Ok(running_node)
     }
-
-    pub fn set_event_callback<F: FnMut(LibwakuResponse) + 'static + Sync + Send>(
-        &self,
-        closure: F,
-    ) -> Result<()> {
-        self.ctx.waku_set_event_callback(closure)
-    }
-
-    /// Return a stream of all Waku responses.
-    pub fn response_stream(&self) -> impl Stream<Item = LibwakuResponse> {
-        let (tx, rx) = unbounded_channel();
-        let tx_clone = tx.clone();
-
-        let callback = {
-            move |event: LibwakuResponse| {
-                let _ = tx_clone.send(event);
-            }
-        };
-
-        if let Err(error) = self.ctx.waku_set_event_callback(callback) {
-            tx.send(LibwakuResponse::Failure(error)).unwrap();
-        }
-
-        let stream = UnboundedReceiverStream::new(rx);
-
-        stream
-    }
 }
 
 impl WakuNodeHandle<Running> {
@@ -120,9 +165,18 @@ impl WakuNodeHandle<Running> {
     pub async fn stop(self) -> Result<WakuNodeHandle<Initialized>> {
         management::waku_stop(&self.ctx).await?;
 
+        // Stop forwarding events into the cache so a subsequent `start()` spawns
+        // exactly one forwarder instead of leaking this one alongside it.
+        if let Some(relay_cache_task) = &self.relay_cache_task {
+            relay_cache_task.abort();
+        }
+
         let init_node = WakuNodeHandle {
             ctx: self.ctx,
             config: self.config,
+            cache: self.cache,
+            dispatcher: self.dispatcher,
+            relay_cache_task: None,
             _state: PhantomData,
         };
 
@@ -144,6 +198,21 @@ impl WakuNodeHandle<Running> {
         peers::waku_connect(&self.ctx, address, timeout).await
     }
 
+    /// Disconnect from a currently connected peer.
+    pub async fn disconnect(&self, peer_id: &str) -> Result<()> {
+        peers::waku_disconnect(&self.ctx, peer_id).await
+    }
+
+    /// List the peers currently known to the node, along with their connectedness.
+    pub async fn connected_peers(&self) -> Result<Vec<PeerInfo>> {
+        peers::waku_connected_peers(&self.ctx).await
+    }
+
+    /// Count the peers currently known to the node.
+    pub async fn peer_count(&self) -> Result<usize> {
+        Ok(self.connected_peers().await?.len())
+    }
+
     /// Publish a message using Waku Relay.
     /// As per the [specification](https://rfc.vac.dev/spec/36/#extern-char-waku_relay_publishchar-messagejson-char-pubsubtopic-int-timeoutms)
     /// The pubsub_topic parameter is optional and if not specified it will be derived from the contentTopic.
@@ -190,6 +259,14 @@ impl WakuNodeHandle<Running> {
         relay::waku_relay_unsubscribe(&self.ctx, pubsub_topic.into()).await
     }
 
+    /// Subscribe to receive messages matching a content topic, deriving the pubsub
+    /// topic via autosharding as per [RFC 51](https://rfc.vac.dev/spec/51/).
+    /// Requires the node to have been configured with a [`ShardingConfig`].
+    pub async fn relay_subscribe_autoshard(&self, content_topic: &WakuContentTopic) -> Result<()> {
+        let pubsub_topic = self.autoshard(content_topic)?;
+        self.relay_subscribe(pubsub_topic).await
+    }
+
     pub async fn filter_subscribe(
         &self,
         pubsub_topic: PubsubTopic,
@@ -198,6 +275,56 @@ impl WakuNodeHandle<Running> {
         filter::waku_filter_subscribe(&self.ctx, pubsub_topic, content_topics).await
     }
 
+    /// Subscribe via Filter using only content topics, deriving the pubsub topic
+    /// for each one via autosharding.
+    ///
+    /// If a content topic partway through fails to subscribe, the ones already
+    /// subscribed in this call are rolled back (best-effort) before returning the
+    /// error. Unsubscribe errors during that rollback are ignored, so a failed call
+    /// can still leave some of its earlier subscriptions in place.
+    pub async fn filter_subscribe_autoshard(
+        &self,
+        content_topics: Vec<WakuContentTopic>,
+    ) -> Result<()> {
+        let mut subscribed = Vec::new();
+
+        for content_topic in &content_topics {
+            let pubsub_topic = match self.autoshard(content_topic) {
+                Ok(pubsub_topic) => pubsub_topic,
+                Err(error) => {
+                    self.rollback_filter_subscriptions(subscribed).await;
+                    return Err(error);
+                }
+            };
+
+            match filter::waku_filter_subscribe(
+                &self.ctx,
+                pubsub_topic.clone(),
+                vec![content_topic.clone()],
+            )
+            .await
+            {
+                Ok(()) => subscribed.push((pubsub_topic, content_topic.clone())),
+                Err(error) => {
+                    self.rollback_filter_subscriptions(subscribed).await;
+                    return Err(error);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Best-effort unsubscribe of topics already subscribed by a
+    /// [`filter_subscribe_autoshard`](Self::filter_subscribe_autoshard) call that
+    /// failed partway through. Errors here are swallowed: the caller already has
+    /// the original error to act on, and there's no better recovery to offer.
+    async fn rollback_filter_subscriptions(&self, subscribed: Vec<(PubsubTopic, WakuContentTopic)>) {
+        for (pubsub_topic, content_topic) in subscribed {
+            let _ = filter::waku_filter_unsubscribe(&self.ctx, pubsub_topic, vec![content_topic]).await;
+        }
+    }
+
     pub async fn filter_unsubscribe(
         &self,
         pubsub_topic: PubsubTopic,
@@ -218,6 +345,25 @@ impl WakuNodeHandle<Running> {
         lightpush::waku_lightpush_publish_message(&self.ctx, message, pubsub_topic).await
     }
 
+    /// Publish a message via Lightpush, deriving the pubsub topic from the message's
+    /// own content topic via autosharding.
+    pub async fn lightpush_publish_message_autoshard(
+        &self,
+        message: &WakuMessage,
+    ) -> Result<MessageHash> {
+        let pubsub_topic = self.autoshard(message.content_topic())?;
+        self.lightpush_publish_message(message, pubsub_topic).await
+    }
+
+    /// Derive the pubsub topic for a content topic using the node's [`ShardingConfig`].
+    fn autoshard(&self, content_topic: &WakuContentTopic) -> Result<PubsubTopic> {
+        let sharding = self.config.sharding.as_ref().ok_or_else(|| {
+            "Autosharding requires the node to be configured with a ShardingConfig".to_string()
+        })?;
+
+        PubsubTopic::autoshard(content_topic, sharding)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn store_query(
         &self,
@@ -258,4 +404,71 @@ impl WakuNodeHandle<Running> {
 
         Ok(messages)
     }
+
+    /// Query a Store peer for historical messages, yielding each message as its page
+    /// arrives instead of buffering the whole result set in memory. Pages are only
+    /// fetched as the returned stream is polled, so dropping the stream early cancels
+    /// further pagination.
+    ///
+    /// Paginates backward, the same direction [`store_query`](Self::store_query)
+    /// ends up returning after its reversal, so messages are yielded newest-first
+    /// here too.
+    #[allow(clippy::too_many_arguments)]
+    pub fn store_query_stream(
+        &self,
+        pubsub_topic: Option<PubsubTopic>,
+        content_topics: Vec<WakuContentTopic>,
+        peer_addr: String,
+        include_data: bool,
+        time_start: Option<u64>,
+        time_end: Option<u64>,
+        timeout_millis: Option<Duration>,
+    ) -> impl Stream<Item = Result<StoreWakuMessageResponse>> + '_ {
+        try_stream! {
+            let mut cursor: Option<MessageHash> = None;
+
+            loop {
+                let query = StoreQueryRequest::new()
+                    .with_pubsub_topic(pubsub_topic.clone())
+                    .with_content_topics(content_topics.clone())
+                    .with_include_data(include_data)
+                    .with_time_start(time_start)
+                    .with_time_end(time_end)
+                    .with_pagination_cursor(cursor)
+                    .with_pagination_forward(false);
+
+                let response =
+                    store::waku_store_query(&self.ctx, query, &peer_addr, timeout_millis).await?;
+
+                for message in response.messages {
+                    yield message;
+                }
+
+                if response.pagination_cursor.is_none() {
+                    break;
+                }
+                cursor = response.pagination_cursor;
+            }
+        }
+    }
+
+    /// Answer a Store-style query from the in-memory cache of messages observed via
+    /// `relay_subscribe`/`filter_subscribe`, without dialing a remote Store peer.
+    /// Useful for nodes that subscribe to Relay/Filter but cannot reach a Store service.
+    pub async fn local_store_query(
+        &self,
+        pubsub_topic: Option<PubsubTopic>,
+        content_topics: Vec<WakuContentTopic>,
+        include_data: bool,
+        time_start: Option<u64>,
+        time_end: Option<u64>,
+    ) -> Result<Vec<StoreWakuMessageResponse>> {
+        Ok(self.cache.query(
+            pubsub_topic.as_ref(),
+            &content_topics,
+            time_start,
+            time_end,
+            include_data,
+        ))
+    }
 }
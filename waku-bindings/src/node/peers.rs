@@ -0,0 +1,77 @@
+//! Peer management
+//! as per the [specification](https://rfc.vac.dev/spec/36/#peer-management)
+
+// std
+use std::ffi::CString;
+use std::time::Duration;
+// crates
+use multiaddr::Multiaddr;
+use serde::{Deserialize, Serialize};
+// internal
+use crate::general::libwaku_response::{handle_no_response, handle_response};
+use crate::general::Result;
+use crate::handle_ffi_call;
+use crate::node::context::WakuNodeContext;
+
+/// Connectedness of a peer, as reported by libp2p.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Connectedness {
+    NotConnected,
+    CanConnect,
+    CannotConnect,
+    Connected,
+}
+
+/// Information about a peer known to the node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerInfo {
+    pub peer_id: String,
+    pub addrs: Vec<Multiaddr>,
+    pub protocols: Vec<String>,
+    pub connectedness: Connectedness,
+}
+
+/// Dial peer using a multiaddress
+/// If `timeout` as milliseconds doesn't fit into a `i32` it is clamped to [`i32::MAX`]
+/// If the function execution takes longer than `timeout` value, the execution will be canceled and an error returned.
+/// Use 0 for no timeout
+/// As per the [specification](https://rfc.vac.dev/spec/36/#extern-char-waku_connect_peerchar-address-int-timeoutms)
+pub(crate) async fn waku_connect(
+    ctx: &WakuNodeContext,
+    address: &Multiaddr,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let address =
+        CString::new(address.to_string()).expect("CString should build properly from the address");
+    let timeout_ms = timeout
+        .map(|timeout| timeout.as_millis().try_into().unwrap_or(i32::MAX))
+        .unwrap_or(0);
+
+    handle_ffi_call!(
+        waku_sys::waku_connect,
+        handle_no_response,
+        ctx.get_ptr(),
+        address.as_ptr(),
+        timeout_ms
+    )
+}
+
+/// Disconnect from a peer by id.
+/// As per the [specification](https://rfc.vac.dev/spec/36/#extern-char-waku_disconnect_peerchar-peerid)
+pub(crate) async fn waku_disconnect(ctx: &WakuNodeContext, peer_id: &str) -> Result<()> {
+    let peer_id = CString::new(peer_id).expect("CString should build properly from the peer id");
+
+    handle_ffi_call!(
+        waku_sys::waku_disconnect_peer,
+        handle_no_response,
+        ctx.get_ptr(),
+        peer_id.as_ptr()
+    )
+}
+
+/// List the peers currently known to the node, along with their connectedness.
+/// As per the [specification](https://rfc.vac.dev/spec/36/#extern-char-waku_list_peers)
+pub(crate) async fn waku_connected_peers(ctx: &WakuNodeContext) -> Result<Vec<PeerInfo>> {
+    handle_ffi_call!(waku_sys::waku_list_peers, handle_response, ctx.get_ptr())
+}
@@ -0,0 +1,146 @@
+//! In-memory cache of messages observed via Relay/Filter, answering Store-style
+//! queries locally for nodes that cannot reach a remote Store peer.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use crate::general::contenttopic::WakuContentTopic;
+use crate::general::pubsubtopic::PubsubTopic;
+use crate::node::events::WakuMessageEvent;
+use crate::node::store::StoreWakuMessageResponse;
+
+const DEFAULT_CAPACITY: usize = 1_000;
+
+struct CachedMessage {
+    pubsub_topic: PubsubTopic,
+    content_topic: WakuContentTopic,
+    timestamp: u64,
+    response: StoreWakuMessageResponse,
+}
+
+/// A bounded ring buffer of messages tagged with their `(PubsubTopic, WakuContentTopic)`,
+/// populated from the node's event callback and queried by
+/// [`local_store_query`](super::WakuNodeHandle::local_store_query).
+///
+/// All topics share the one buffer and its capacity: once full, the oldest message
+/// is evicted whatever its topic. Topics are matched by scanning at query time.
+pub(crate) struct RelayCache {
+    capacity: usize,
+    messages: Mutex<VecDeque<CachedMessage>>,
+}
+
+impl RelayCache {
+    pub(crate) fn new(capacity: Option<usize>) -> Self {
+        Self {
+            capacity: capacity.unwrap_or(DEFAULT_CAPACITY),
+            messages: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub(crate) fn insert(&self, event: WakuMessageEvent) {
+        let mut messages = self
+            .messages
+            .lock()
+            .expect("relay cache lock should not be poisoned");
+
+        if messages.len() >= self.capacity {
+            messages.pop_front();
+        }
+
+        messages.push_back(CachedMessage {
+            pubsub_topic: event.pubsub_topic.clone(),
+            content_topic: event.content_topic().clone(),
+            timestamp: event.waku_message.timestamp(),
+            response: StoreWakuMessageResponse {
+                message_hash: event.message_hash.clone(),
+                message: Some(event.waku_message),
+                pubsub_topic: event.pubsub_topic,
+            },
+        });
+    }
+
+    pub(crate) fn query(
+        &self,
+        pubsub_topic: Option<&PubsubTopic>,
+        content_topics: &[WakuContentTopic],
+        time_start: Option<u64>,
+        time_end: Option<u64>,
+        include_data: bool,
+    ) -> Vec<StoreWakuMessageResponse> {
+        let messages = self
+            .messages
+            .lock()
+            .expect("relay cache lock should not be poisoned");
+
+        messages
+            .iter()
+            .filter(|cached| {
+                pubsub_topic.is_none_or(|topic| &cached.pubsub_topic == topic)
+                    && (content_topics.is_empty()
+                        || content_topics.contains(&cached.content_topic))
+                    && time_start.is_none_or(|start| cached.timestamp >= start)
+                    && time_end.is_none_or(|end| cached.timestamp <= end)
+            })
+            .map(|cached| {
+                let mut response = cached.response.clone();
+                if !include_data {
+                    response.message = None;
+                }
+                response
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::general::WakuMessage;
+
+    fn message_event(content_topic_name: &str, timestamp: u64) -> WakuMessageEvent {
+        let content_topic =
+            WakuContentTopic::new("toychat", 2, content_topic_name.to_string(), "proto");
+
+        WakuMessageEvent {
+            pubsub_topic: PubsubTopic::from("/waku/2/default-waku/proto"),
+            message_hash: format!("0x{content_topic_name}{timestamp}").into(),
+            waku_message: WakuMessage::new(vec![1, 2, 3], content_topic, timestamp),
+        }
+    }
+
+    #[test]
+    fn query_filters_by_content_topic_and_time_window() {
+        let cache = RelayCache::new(None);
+        cache.insert(message_event("huilong", 100));
+        cache.insert(message_event("other", 200));
+
+        let huilong = WakuContentTopic::new("toychat", 2, "huilong", "proto");
+        let results = cache.query(None, &[huilong], Some(50), Some(150), true);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].message.as_ref().unwrap().timestamp(), 100);
+    }
+
+    #[test]
+    fn query_strips_payload_when_include_data_is_false() {
+        let cache = RelayCache::new(None);
+        cache.insert(message_event("huilong", 100));
+
+        let results = cache.query(None, &[], None, None, false);
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].message.is_none());
+    }
+
+    #[test]
+    fn insert_evicts_oldest_entry_once_capacity_is_reached() {
+        let cache = RelayCache::new(Some(1));
+        cache.insert(message_event("huilong", 100));
+        cache.insert(message_event("huilong", 200));
+
+        let results = cache.query(None, &[], None, None, true);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].message.as_ref().unwrap().timestamp(), 200);
+    }
+}
@@ -0,0 +1,113 @@
+//! Store protocol queries
+//! as per the [specification](https://rfc.vac.dev/spec/13/)
+
+// std
+use std::ffi::CString;
+use std::time::Duration;
+// crates
+use serde::{Deserialize, Serialize};
+// internal
+use crate::general::contenttopic::WakuContentTopic;
+use crate::general::messagehash::MessageHash;
+use crate::general::libwaku_response::handle_response;
+use crate::general::pubsubtopic::PubsubTopic;
+use crate::general::{Result, WakuMessage};
+use crate::handle_ffi_call;
+use crate::node::context::WakuNodeContext;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreQueryRequest {
+    pubsub_topic: Option<PubsubTopic>,
+    content_topics: Vec<WakuContentTopic>,
+    include_data: bool,
+    time_start: Option<u64>,
+    time_end: Option<u64>,
+    pagination_cursor: Option<MessageHash>,
+    pagination_forward: bool,
+}
+
+impl StoreQueryRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_pubsub_topic(mut self, pubsub_topic: Option<PubsubTopic>) -> Self {
+        self.pubsub_topic = pubsub_topic;
+        self
+    }
+
+    pub fn with_content_topics(mut self, content_topics: Vec<WakuContentTopic>) -> Self {
+        self.content_topics = content_topics;
+        self
+    }
+
+    pub fn with_include_data(mut self, include_data: bool) -> Self {
+        self.include_data = include_data;
+        self
+    }
+
+    pub fn with_time_start(mut self, time_start: Option<u64>) -> Self {
+        self.time_start = time_start;
+        self
+    }
+
+    pub fn with_time_end(mut self, time_end: Option<u64>) -> Self {
+        self.time_end = time_end;
+        self
+    }
+
+    pub fn with_pagination_cursor(mut self, pagination_cursor: Option<MessageHash>) -> Self {
+        self.pagination_cursor = pagination_cursor;
+        self
+    }
+
+    pub fn with_pagination_forward(mut self, pagination_forward: bool) -> Self {
+        self.pagination_forward = pagination_forward;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreWakuMessageResponse {
+    pub message_hash: MessageHash,
+    pub message: Option<WakuMessage>,
+    pub pubsub_topic: PubsubTopic,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StoreQueryResponse {
+    pub messages: Vec<StoreWakuMessageResponse>,
+    pub pagination_cursor: Option<MessageHash>,
+}
+
+/// Query a Store peer for historical messages
+/// as per the [specification](https://rfc.vac.dev/spec/13/#waku-store-query-request)
+pub(crate) async fn waku_store_query(
+    ctx: &WakuNodeContext,
+    query: StoreQueryRequest,
+    peer_addr: &str,
+    timeout: Option<Duration>,
+) -> Result<StoreQueryResponse> {
+    let query = CString::new(
+        serde_json::to_string(&query)
+            .expect("Serialization of StoreQueryRequest should never fail"),
+    )
+    .expect("CString should build properly from the query");
+    let peer_addr =
+        CString::new(peer_addr).expect("CString should build properly from the peer address");
+    let timeout_ms = timeout
+        .map(|timeout| timeout.as_millis().try_into().unwrap_or(i32::MAX))
+        .unwrap_or(0);
+
+    handle_ffi_call!(
+        waku_sys::waku_store_query,
+        handle_response,
+        ctx.get_ptr(),
+        query.as_ptr(),
+        peer_addr.as_ptr(),
+        timeout_ms
+    )
+}